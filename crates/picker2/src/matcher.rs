@@ -0,0 +1,246 @@
+//! A small skim/Smith-Waterman-style fuzzy matcher shared by picker delegates.
+//!
+//! Delegates can call [`fuzzy_match`] from `PickerDelegate::update_matches` to
+//! score and sort candidates, and pass the returned indices into
+//! `render_match` to highlight the characters that matched.
+
+use std::cmp;
+
+const MATCH_SCORE: i32 = 16;
+const GAP_PENALTY: i32 = 3;
+const CONSECUTIVE_BONUS: i32 = 8;
+const WORD_BOUNDARY_BONUS: i32 = 8;
+const CAMEL_CASE_BONUS: i32 = 6;
+const FIRST_CHAR_BONUS: i32 = 4;
+const EXACT_CASE_BONUS: i32 = 1;
+
+const NEG_INFINITY: i32 = i32::MIN / 2;
+
+/// Scores `candidate` against `query`, returning the match score together
+/// with the indices into `candidate` that were matched, or `None` if `query`
+/// cannot be matched against `candidate` in order.
+///
+/// Matching is case-insensitive, but exact-case matches are given a small
+/// bonus, as are matches that land on a word boundary (after a separator, or
+/// at a camelCase hump) or at the very start of `candidate`. Consecutive runs
+/// of matched characters compound their bonus, while skipping characters
+/// between matches accrues a gap penalty proportional to the distance
+/// skipped.
+pub fn fuzzy_match(query: &str, candidate: &str) -> Option<(i32, Vec<usize>)> {
+    let query_chars = query.chars().collect::<Vec<_>>();
+    let candidate_chars = candidate.chars().collect::<Vec<_>>();
+    let m = query_chars.len();
+    let n = candidate_chars.len();
+    if m == 0 || n == 0 || m > n {
+        return None;
+    }
+
+    // `best[i][j]` is the best score matching `query[..i]` somewhere within
+    // `candidate[..j]`. `match_score[i][j]` is the best score matching
+    // `query[..i]` with the i-th query char landing exactly on `candidate[j - 1]`.
+    let mut best = vec![vec![0; n + 1]; m + 1];
+    let mut match_score = vec![vec![NEG_INFINITY; n + 1]; m + 1];
+    let mut consecutive = vec![vec![0usize; n + 1]; m + 1];
+
+    for row in best[1..=m].iter_mut() {
+        row[0] = NEG_INFINITY;
+    }
+
+    for i in 1..=m {
+        for j in 1..=n {
+            let carried = if best[i][j - 1] > NEG_INFINITY {
+                best[i][j - 1] - GAP_PENALTY
+            } else {
+                NEG_INFINITY
+            };
+
+            if chars_match(query_chars[i - 1], candidate_chars[j - 1]) {
+                let bonus = boundary_bonus(&candidate_chars, j - 1)
+                    + case_bonus(query_chars[i - 1], candidate_chars[j - 1]);
+
+                let from_run = if match_score[i - 1][j - 1] > NEG_INFINITY {
+                    Some((
+                        match_score[i - 1][j - 1]
+                            + MATCH_SCORE
+                            + bonus
+                            + consecutive[i - 1][j - 1] as i32 * CONSECUTIVE_BONUS,
+                        consecutive[i - 1][j - 1] + 1,
+                    ))
+                } else {
+                    None
+                };
+                let from_best = if best[i - 1][j - 1] > NEG_INFINITY {
+                    Some((best[i - 1][j - 1] + MATCH_SCORE + bonus, 1))
+                } else {
+                    None
+                };
+
+                let (score, run) = match (from_run, from_best) {
+                    (Some(run), Some(fresh)) if run.0 >= fresh.0 => run,
+                    (Some(run), None) => run,
+                    (_, Some(fresh)) => fresh,
+                    (None, None) => (NEG_INFINITY, 0),
+                };
+
+                match_score[i][j] = score;
+                consecutive[i][j] = run;
+                best[i][j] = cmp::max(score, carried);
+            } else {
+                match_score[i][j] = NEG_INFINITY;
+                best[i][j] = carried;
+            }
+        }
+    }
+
+    // The query can be fully matched at any column >= m; take the best of
+    // those rather than `best[m][n]` unconditionally, otherwise unmatched
+    // trailing candidate characters keep accruing `GAP_PENALTY` for no
+    // reason and penalize an early, clean match against a longer candidate.
+    let (best_score, best_j) = (m..=n)
+        .map(|j| (best[m][j], j))
+        .max_by_key(|(score, _)| *score)?;
+    if best_score <= NEG_INFINITY {
+        return None;
+    }
+
+    let mut indices = Vec::with_capacity(m);
+    let mut i = m;
+    let mut j = best_j;
+    // A cell that extended a consecutive run was scored from the match right
+    // before it, so that match must be the next index, not the best cell.
+    let mut in_run = false;
+    while i > 0 {
+        while !in_run && j > 0 && match_score[i][j] != best[i][j] {
+            j -= 1;
+        }
+        indices.push(j - 1);
+        in_run = consecutive[i][j] > 1;
+        i -= 1;
+        j -= 1;
+    }
+    indices.reverse();
+
+    Some((best_score, indices))
+}
+
+fn chars_match(query_char: char, candidate_char: char) -> bool {
+    query_char.eq_ignore_ascii_case(&candidate_char) || query_char == candidate_char
+}
+
+fn case_bonus(query_char: char, candidate_char: char) -> i32 {
+    if query_char == candidate_char {
+        EXACT_CASE_BONUS
+    } else {
+        0
+    }
+}
+
+/// A bonus for landing on a "natural" boundary within `candidate`: the very
+/// first character, right after a separator, or at a camelCase hump.
+fn boundary_bonus(candidate_chars: &[char], ix: usize) -> i32 {
+    if ix == 0 {
+        return FIRST_CHAR_BONUS;
+    }
+
+    let prev = candidate_chars[ix - 1];
+    let current = candidate_chars[ix];
+    if is_word_separator(prev) {
+        WORD_BOUNDARY_BONUS
+    } else if prev.is_lowercase() && current.is_uppercase() {
+        CAMEL_CASE_BONUS
+    } else {
+        0
+    }
+}
+
+fn is_word_separator(c: char) -> bool {
+    matches!(c, '/' | '_' | '-' | '.' | ' ')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn indices(query: &str, candidate: &str) -> Vec<usize> {
+        fuzzy_match(query, candidate).unwrap().1
+    }
+
+    fn score(query: &str, candidate: &str) -> i32 {
+        fuzzy_match(query, candidate).unwrap().0
+    }
+
+    #[test]
+    fn empty_query_does_not_match() {
+        assert_eq!(fuzzy_match("", "anything"), None);
+    }
+
+    #[test]
+    fn empty_candidate_does_not_match() {
+        assert_eq!(fuzzy_match("a", ""), None);
+    }
+
+    #[test]
+    fn query_longer_than_candidate_does_not_match() {
+        assert_eq!(fuzzy_match("abcd", "abc"), None);
+    }
+
+    #[test]
+    fn out_of_order_query_does_not_match() {
+        assert_eq!(fuzzy_match("ba", "ab"), None);
+    }
+
+    #[test]
+    fn matches_in_order_recovers_indices() {
+        assert_eq!(indices("ac", "abc"), vec![0, 2]);
+        assert_eq!(indices("abc", "abc"), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn trailing_unmatched_text_does_not_reduce_score() {
+        // A clean match followed by more candidate text shouldn't score any
+        // worse than the same match against the bare prefix.
+        assert_eq!(score("abc", "abc"), score("abc", "abcdefghijklmnopqrst"));
+    }
+
+    #[test]
+    fn early_clean_match_beats_match_after_a_long_skip() {
+        let early = score("abc", "abcdefghijklmnopqrst");
+        let late = score("abc", "xxxxxxxxxxxxxxxxxxxxabc");
+        assert!(
+            early > late,
+            "expected early match ({}) to outscore a match reached after a long skip ({})",
+            early,
+            late,
+        );
+    }
+
+    #[test]
+    fn consecutive_run_outscores_scattered_match() {
+        let consecutive = score("abc", "abcxxxxx");
+        let scattered = score("abc", "a-b-c-xx");
+        assert!(consecutive > scattered);
+    }
+
+    #[test]
+    fn word_boundary_match_outscores_mid_word_match() {
+        // matching the `f` in `foo` after the `_` separator should score
+        // higher than matching the `f` in the middle of a word.
+        let boundary = score("f", "bar_foo");
+        let mid_word = score("f", "barfoo");
+        assert!(boundary > mid_word);
+    }
+
+    #[test]
+    fn camel_case_boundary_is_bonused() {
+        let camel = score("f", "barFoo");
+        let mid_word = score("f", "barfoo");
+        assert!(camel > mid_word);
+    }
+
+    #[test]
+    fn exact_case_beats_case_insensitive_match() {
+        let exact = score("F", "Foo");
+        let insensitive = score("F", "foo");
+        assert!(exact > insensitive);
+    }
+}
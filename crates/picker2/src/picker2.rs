@@ -1,51 +1,143 @@
+mod matcher;
+
+pub use matcher::fuzzy_match;
+
 use editor::Editor;
 use gpui::{
-    div, uniform_list, Component, Div, FocusEnabled, ParentElement, Render, StatefulInteractivity,
-    StatelessInteractive, Styled, Task, UniformListScrollHandle, View, ViewContext, VisualContext,
-    WindowContext,
+    canvas, div, point, px, rgba, uniform_list, Bounds, Component, Div, FocusEnabled,
+    ParentElement, Pixels, Render, ScrollHandle, StatefulInteractivity, StatelessInteractive,
+    Styled, Task, UniformListScrollHandle, View, ViewContext, VisualContext, WindowContext,
 };
 use std::cmp;
+use std::ops::Range;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Below this container width (in logical pixels) the preview pane is hidden
+/// and the picker falls back to a list-only layout.
+const DEFAULT_MIN_WIDTH_FOR_PREVIEW: f32 = 72.0 * 8.0;
+
+/// Assumed line height (in logical pixels) used to translate a preview's
+/// highlighted line range into a scroll offset and an overlay size.
+/// Delegates whose preview content uses a different line height should scale
+/// their highlight ranges to match.
+const PREVIEW_LINE_HEIGHT: f32 = 20.0;
 
 pub struct Picker<D: PickerDelegate> {
     pub delegate: D,
     scroll_handle: UniformListScrollHandle,
+    preview_scroll_handle: ScrollHandle,
     editor: View<Editor>,
     pending_update_matches: Option<Task<Option<()>>>,
+    min_width_for_preview: f32,
+    container_width: Option<f32>,
+    preview_cache: Option<(usize, usize, D::PreviewItem, Option<Range<usize>>)>,
+    debounce: Option<Duration>,
+    matches_generation: usize,
+    truncate_start: bool,
 }
 
 pub trait PickerDelegate: Sized + 'static {
     type ListItem: Component<Picker<Self>>;
+    type PreviewItem: Component<Picker<Self>> + Clone;
 
     fn match_count(&self) -> usize;
     fn selected_index(&self) -> usize;
     fn set_selected_index(&mut self, ix: usize, cx: &mut ViewContext<Picker<Self>>);
 
-    // fn placeholder_text(&self) -> Arc<str>;
+    /// Prompt text shown in the picker's query editor while it's empty, e.g.
+    /// "Search files…" or "Go to symbol…".
+    fn placeholder_text(&self) -> Arc<str> {
+        Arc::from("")
+    }
+
     fn update_matches(&mut self, query: String, cx: &mut ViewContext<Picker<Self>>) -> Task<()>;
 
     fn confirm(&mut self, secondary: bool, cx: &mut ViewContext<Picker<Self>>);
     fn dismissed(&mut self, cx: &mut ViewContext<Picker<Self>>);
 
+    /// Renders the match at `ix`. When the picker's `truncate_start` mode is
+    /// enabled, long candidate strings (e.g. deep file paths) should be
+    /// elided on the left so that the most-specific tail (the filename)
+    /// stays visible, rather than the default of eliding on the right.
     fn render_match(
         &self,
         ix: usize,
         selected: bool,
+        truncate_start: bool,
         cx: &mut ViewContext<Picker<Self>>,
     ) -> Self::ListItem;
+
+    /// Renders a preview of the item at `ix`, shown alongside the match list
+    /// when the picker is wide enough, together with an optional line range
+    /// that should be scrolled into view and highlighted (e.g. the matched
+    /// lines for a symbol or search result). Delegates whose content exceeds
+    /// `max_preview_size` should return `None` or a truncated preview rather
+    /// than rendering the item in full.
+    fn render_preview(
+        &self,
+        _ix: usize,
+        _cx: &mut ViewContext<Picker<Self>>,
+    ) -> Option<(Self::PreviewItem, Option<Range<usize>>)> {
+        None
+    }
+
+    /// The largest amount of content (bytes or lines, delegate-defined) this
+    /// delegate is willing to preview. `render_preview` should honor this by
+    /// returning `None` or a truncated view for larger items.
+    fn max_preview_size(&self) -> Option<usize> {
+        None
+    }
 }
 
 impl<D: PickerDelegate> Picker<D> {
     pub fn new(delegate: D, cx: &mut ViewContext<Self>) -> Self {
-        let editor = cx.build_view(|cx| Editor::single_line(cx));
+        let placeholder_text = delegate.placeholder_text();
+        let editor = cx.build_view(|cx| {
+            let mut editor = Editor::single_line(cx);
+            editor.set_placeholder_text(placeholder_text, cx);
+            editor
+        });
         cx.subscribe(&editor, Self::on_input_editor_event).detach();
         Self {
             delegate,
             scroll_handle: UniformListScrollHandle::new(),
+            preview_scroll_handle: ScrollHandle::new(),
             pending_update_matches: None,
             editor,
+            min_width_for_preview: DEFAULT_MIN_WIDTH_FOR_PREVIEW,
+            container_width: None,
+            preview_cache: None,
+            debounce: None,
+            matches_generation: 0,
+            truncate_start: false,
         }
     }
 
+    /// When enabled, delegates are told to elide long match text on the left
+    /// rather than the right, keeping the most-specific tail (e.g. a
+    /// filename at the end of a long path) visible.
+    pub fn truncate_start(mut self, truncate_start: bool) -> Self {
+        self.truncate_start = truncate_start;
+        self
+    }
+
+    /// Sets the minimum container width (in logical pixels) below which the
+    /// preview pane is collapsed in favor of a list-only layout.
+    pub fn min_width_for_preview(mut self, min_width_for_preview: f32) -> Self {
+        self.min_width_for_preview = min_width_for_preview;
+        self
+    }
+
+    /// Sets how long to wait after the most recent query before asking the
+    /// delegate to update its matches, coalescing rapid keystrokes so an
+    /// expensive delegate (directory scans, network queries) isn't re-run on
+    /// every edit.
+    pub fn with_debounce(mut self, debounce: Duration) -> Self {
+        self.debounce = Some(debounce);
+        self
+    }
+
     pub fn focus(&self, cx: &mut WindowContext) {
         self.editor.update(cx, |editor, cx| editor.focus(cx));
     }
@@ -57,6 +149,7 @@ impl<D: PickerDelegate> Picker<D> {
             let ix = cmp::min(index + 1, count - 1);
             self.delegate.set_selected_index(ix, cx);
             self.scroll_handle.scroll_to_item(ix);
+            self.update_preview(ix, cx);
         }
     }
 
@@ -67,6 +160,7 @@ impl<D: PickerDelegate> Picker<D> {
             let ix = index.saturating_sub(1);
             self.delegate.set_selected_index(ix, cx);
             self.scroll_handle.scroll_to_item(ix);
+            self.update_preview(ix, cx);
         }
     }
 
@@ -75,6 +169,7 @@ impl<D: PickerDelegate> Picker<D> {
         if count > 0 {
             self.delegate.set_selected_index(0, cx);
             self.scroll_handle.scroll_to_item(0);
+            self.update_preview(0, cx);
         }
     }
 
@@ -83,9 +178,48 @@ impl<D: PickerDelegate> Picker<D> {
         if count > 0 {
             self.delegate.set_selected_index(count - 1, cx);
             self.scroll_handle.scroll_to_item(count - 1);
+            self.update_preview(count - 1, cx);
         }
     }
 
+    /// Re-renders the preview for `ix`, reusing the cached preview when
+    /// neither the selection nor the match-set generation have changed, so
+    /// that rapid navigation doesn't re-render from scratch. The generation
+    /// is part of the cache key so a requery that leaves the selected index
+    /// unchanged (e.g. resetting to the first match) still invalidates the
+    /// preview of the item that used to be at that index.
+    ///
+    /// When the delegate returns a highlight range alongside the preview,
+    /// the preview's scroll position is moved to the start of that range so
+    /// it's visible as soon as the preview is shown; `render` overlays a
+    /// highlight for the full range.
+    fn update_preview(&mut self, ix: usize, cx: &mut ViewContext<Self>) {
+        let generation = self.matches_generation;
+        if self
+            .preview_cache
+            .as_ref()
+            .is_some_and(|(cached_generation, cached_ix, _, _)| {
+                *cached_generation == generation && *cached_ix == ix
+            })
+        {
+            return;
+        }
+
+        let Some((item, highlight_lines)) = self.delegate.render_preview(ix, cx) else {
+            self.preview_cache = None;
+            return;
+        };
+
+        if let Some(highlight_lines) = &highlight_lines {
+            self.preview_scroll_handle.scroll_to(point(
+                px(0.),
+                px(highlight_lines.start as f32 * PREVIEW_LINE_HEIGHT),
+            ));
+        }
+
+        self.preview_cache = Some((generation, ix, item, highlight_lines));
+    }
+
     fn cancel(&mut self, _: &menu::Cancel, cx: &mut ViewContext<Self>) {
         self.delegate.dismissed(cx);
     }
@@ -110,13 +244,57 @@ impl<D: PickerDelegate> Picker<D> {
         }
     }
 
+    /// Asks the delegate to update its matches for `query`. If a `debounce`
+    /// is configured, the delegate isn't touched until the debounce interval
+    /// elapses without a newer query arriving, and replacing
+    /// `pending_update_matches` drops (and so cancels) any update still in
+    /// flight. A generation counter double-checks staleness so a delayed
+    /// result can never clobber a newer one. The UI refreshes once right
+    /// after `delegate.update_matches` returns (picking up whatever its
+    /// synchronous portion already produced) and again once its task
+    /// completes, so an expensive delegate doesn't leave the list frozen on
+    /// the previous query for the whole async duration.
     pub fn update_matches(&mut self, query: String, cx: &mut ViewContext<Self>) {
-        let update = self.delegate.update_matches(query, cx);
-        self.matches_updated(cx);
+        self.matches_generation += 1;
+        let generation = self.matches_generation;
+        let debounce = self.debounce;
+
         self.pending_update_matches = Some(cx.spawn(|this, mut cx| async move {
+            if let Some(debounce) = debounce {
+                cx.background_executor().timer(debounce).await;
+            }
+
+            let is_current = this
+                .update(&mut cx, |this, _| this.matches_generation == generation)
+                .ok()?;
+            if !is_current {
+                return None;
+            }
+
+            let update = this
+                .update(&mut cx, |this, cx| {
+                    let update = this.delegate.update_matches(query, cx);
+                    if this.matches_generation == generation {
+                        // Refresh without `matches_updated`, which would clear
+                        // `pending_update_matches` and so cancel this task.
+                        let index = this.delegate.selected_index();
+                        this.scroll_handle.scroll_to_item(index);
+                        this.update_preview(index, cx);
+                        cx.notify();
+                    }
+                    update
+                })
+                .ok()?;
             update.await;
+
             this.update(&mut cx, |this, cx| {
-                this.matches_updated(cx);
+                if this.matches_generation == generation {
+                    // The synchronous refresh cached a preview under this
+                    // same generation, possibly of the item that used to be
+                    // at the selected index.
+                    this.preview_cache = None;
+                    this.matches_updated(cx);
+                }
             })
             .ok()
         }));
@@ -126,6 +304,7 @@ impl<D: PickerDelegate> Picker<D> {
         let index = self.delegate.selected_index();
         self.scroll_handle.scroll_to_item(index);
         self.pending_update_matches = None;
+        self.update_preview(index, cx);
         cx.notify();
     }
 }
@@ -133,7 +312,16 @@ impl<D: PickerDelegate> Picker<D> {
 impl<D: PickerDelegate> Render for Picker<D> {
     type Element = Div<Self, StatefulInteractivity<Self>, FocusEnabled<Self>>;
 
-    fn render(&mut self, _cx: &mut ViewContext<Self>) -> Self::Element {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> Self::Element {
+        let show_preview = self
+            .container_width
+            .is_some_and(|width| width >= self.min_width_for_preview);
+        let preview = show_preview
+            .then(|| self.preview_cache.clone())
+            .flatten()
+            .map(|(_, _, item, highlight_lines)| (item, highlight_lines));
+        let preview_scroll_handle = self.preview_scroll_handle.clone();
+
         div()
             .context("picker")
             .id("picker-container")
@@ -148,16 +336,66 @@ impl<D: PickerDelegate> Render for Picker<D> {
             .on_action(Self::secondary_confirm)
             .child(self.editor.clone())
             .child(
-                uniform_list("candidates", self.delegate.match_count(), {
-                    move |this: &mut Self, visible_range, cx| {
-                        let selected_ix = this.delegate.selected_index();
-                        visible_range
-                            .map(|ix| this.delegate.render_match(ix, ix == selected_ix, cx))
-                            .collect()
-                    }
-                })
-                .track_scroll(self.scroll_handle.clone())
-                .size_full(),
+                div()
+                    .flex()
+                    .size_full()
+                    // Reports this row's own layout bounds so the preview can
+                    // be toggled off the picker's actual container width
+                    // rather than the window's viewport width.
+                    .child(
+                        canvas(cx.listener(|this, bounds: Bounds<Pixels>, cx| {
+                            let width = bounds.size.width.0;
+                            if this.container_width != Some(width) {
+                                this.container_width = Some(width);
+                                cx.notify();
+                            }
+                        }))
+                        .absolute()
+                        .size_full(),
+                    )
+                    .child(
+                        uniform_list("candidates", self.delegate.match_count(), {
+                            move |this: &mut Self, visible_range, cx| {
+                                let selected_ix = this.delegate.selected_index();
+                                let truncate_start = this.truncate_start;
+                                visible_range
+                                    .map(|ix| {
+                                        this.delegate.render_match(
+                                            ix,
+                                            ix == selected_ix,
+                                            truncate_start,
+                                            cx,
+                                        )
+                                    })
+                                    .collect()
+                            }
+                        })
+                        .track_scroll(self.scroll_handle.clone())
+                        .flex_1(),
+                    )
+                    .children(preview.map(|(item, highlight_lines)| {
+                        div()
+                            .relative()
+                            .flex_1()
+                            .overflow_y_scroll()
+                            .track_scroll(preview_scroll_handle)
+                            .child(item)
+                            .children(highlight_lines.map(|highlight_lines| {
+                                let top = highlight_lines.start as f32 * PREVIEW_LINE_HEIGHT;
+                                let height = highlight_lines
+                                    .end
+                                    .saturating_sub(highlight_lines.start)
+                                    .max(1) as f32
+                                    * PREVIEW_LINE_HEIGHT;
+                                div()
+                                    .absolute()
+                                    .left_0()
+                                    .w_full()
+                                    .top(px(top))
+                                    .h(px(height))
+                                    .bg(rgba(0xffd60a33))
+                            }))
+                    })),
             )
     }
 }
\ No newline at end of file